@@ -86,6 +86,7 @@ impl pallet_balances::Config for Test {
 parameter_types! {
 		pub const MinVestedTransfer: u64 = 256 * 2;
 		pub static ExistentialDeposit: u64 = 0;
+		pub const MaxVestingSchedules: u32 = 28;
 	}
 impl Config for Test {
 	type Event = ();
@@ -93,6 +94,8 @@ impl Config for Test {
 	type BlockNumberToBalance = Identity;
 	type MinVestedTransfer = MinVestedTransfer;
 	type WeightInfo = ();
+	type MaxLocks = MaxLocks;
+	type MaxVestingSchedules = MaxVestingSchedules;
 }
 type System = frame_system::Module<Test>;
 type Balances = pallet_balances::Module<Test>;
@@ -115,8 +118,9 @@ fn add_locks<T: Config>(who: &T::AccountId, n: u8) {
 }
 
 fn add_vesting_schedule<T: Config>(who: &T::AccountId) -> Result<(), &'static str> {
-	let locked = 100u32;
-	let per_block = 10u32;
+	let per_period = 10u32;
+	let period = 1u32;
+	let period_count = 10u32;
 	let starting_block = 1u32;
 
 	System::<T>::set_block_number(0u32.into());
@@ -124,8 +128,35 @@ fn add_vesting_schedule<T: Config>(who: &T::AccountId) -> Result<(), &'static st
 	// Add schedule to avoid `NotVesting` error.
 	Vesting::<T>::add_vesting_schedule(
 		&who,
-		locked.into(),
-		per_block.into(),
+		per_period.into(),
+		period.into(),
+		period_count,
+		starting_block.into(),
+	)?;
+	Ok(())
+}
+
+fn add_vesting_schedules<T: Config>(who: &T::AccountId, n: u32) -> Result<(), &'static str> {
+	for _ in 0..n {
+		add_vesting_schedule::<T>(who)?;
+	}
+	Ok(())
+}
+
+/// A single-release (cliff) schedule, unlike [`add_vesting_schedule`]'s many small releases.
+fn add_cliff_vesting_schedule<T: Config>(who: &T::AccountId) -> Result<(), &'static str> {
+	let per_period = 100u32;
+	let period = 19u32;
+	let period_count = 1u32;
+	let starting_block = 1u32;
+
+	System::<T>::set_block_number(0u32.into());
+
+	Vesting::<T>::add_vesting_schedule(
+		&who,
+		per_period.into(),
+		period.into(),
+		period_count,
 		starting_block.into(),
 	)?;
 	Ok(())
@@ -189,7 +220,7 @@ benchmarks! {
 		let other_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(other.clone());
 		T::Currency::make_free_balance_be(&other, BalanceOf::<T>::max_value());
 		add_locks::<T>(&other, l as u8);
-		add_vesting_schedule::<T>(&other)?;
+		add_cliff_vesting_schedule::<T>(&other)?;
 		// At block zero, everything is vested.
 		System::<T>::set_block_number(T::BlockNumber::zero());
 		assert_eq!(
@@ -216,7 +247,7 @@ benchmarks! {
 		let other_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(other.clone());
 		T::Currency::make_free_balance_be(&other, BalanceOf::<T>::max_value());
 		add_locks::<T>(&other, l as u8);
-		add_vesting_schedule::<T>(&other)?;
+		add_cliff_vesting_schedule::<T>(&other)?;
 		// At block 20, everything is unvested.
 		System::<T>::set_block_number(20u32.into());
 		assert_eq!(
@@ -249,9 +280,10 @@ benchmarks! {
 		let transfer_amount = T::MinVestedTransfer::get();
 
 		let vesting_schedule = VestingInfo {
-			locked: transfer_amount,
-			per_block: 10u32.into(),
 			starting_block: 1u32.into(),
+			period: 1u32.into(),
+			per_period: transfer_amount,
+			period_count: 1,
 		};
 	}: _(RawOrigin::Signed(caller), target_lookup, vesting_schedule)
 	verify {
@@ -281,9 +313,10 @@ benchmarks! {
 		let transfer_amount = T::MinVestedTransfer::get();
 
 		let vesting_schedule = VestingInfo {
-			locked: transfer_amount,
-			per_block: 10u32.into(),
 			starting_block: 1u32.into(),
+			period: 1u32.into(),
+			per_period: transfer_amount,
+			period_count: 1,
 		};
 	}: _(RawOrigin::Root, source_lookup, target_lookup, vesting_schedule)
 	verify {
@@ -298,6 +331,162 @@ benchmarks! {
 			"Lock not created",
 		);
 	}
+
+	vest_with_many_schedules {
+		let l in 0 .. MaxLocksOf::<T>::get() - 1;
+		let s in 2 .. T::MaxVestingSchedules::get();
+
+		let caller = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		add_locks::<T>(&caller, l as u8);
+		add_vesting_schedules::<T>(&caller, s)?;
+		// At block zero, everything is vested.
+		System::<T>::set_block_number(T::BlockNumber::zero());
+		assert_eq!(
+			Vesting::<T>::vesting_balance(&caller),
+			Some((100 * s).into()),
+			"Vesting schedules not added",
+		);
+	}: vest(RawOrigin::Signed(caller.clone()))
+	verify {
+		// Nothing happened since everything is still vested.
+		assert_eq!(
+			Vesting::<T>::vesting_balance(&caller),
+			Some((100 * s).into()),
+			"Vesting schedules was removed",
+		);
+	}
+
+	merge_schedules {
+		let l in 0 .. MaxLocksOf::<T>::get() - 1;
+		let s in 2 .. T::MaxVestingSchedules::get();
+
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		add_locks::<T>(&caller, l as u8);
+		add_vesting_schedules::<T>(&caller, s)?;
+		System::<T>::set_block_number(T::BlockNumber::zero());
+		assert_eq!(
+			Vesting::<T>::vesting(&caller).unwrap().len(),
+			s as usize,
+			"Schedules not all added",
+		);
+	}: _(RawOrigin::Signed(caller.clone()), 0, 1)
+	verify {
+		assert_eq!(
+			Vesting::<T>::vesting(&caller).unwrap().len(),
+			(s - 1) as usize,
+			"Schedules not merged",
+		);
+	}
+
+	force_remove_vesting_schedule {
+		let l in 0 .. MaxLocksOf::<T>::get() - 1;
+		let s in 0 .. T::MaxVestingSchedules::get() - 1;
+
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let caller_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(caller.clone());
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		add_locks::<T>(&caller, l as u8);
+		add_vesting_schedules::<T>(&caller, s + 1)?;
+		let schedule_index = s;
+
+		System::<T>::set_block_number(T::BlockNumber::zero());
+		assert_eq!(
+			Vesting::<T>::vesting(&caller).unwrap().len(),
+			(s + 1) as usize,
+			"Schedule not added",
+		);
+	}: _(RawOrigin::Root, caller_lookup, schedule_index)
+	verify {
+		if s == 0 {
+			assert!(
+				Vesting::<T>::vesting(&caller).is_none(),
+				"Vesting schedule should be removed",
+			);
+			assert_eq!(
+				Vesting::<T>::vesting_balance(&caller),
+				None,
+				"Vesting lock should be removed",
+			);
+		} else {
+			assert_eq!(
+				Vesting::<T>::vesting(&caller).unwrap().len(),
+				s as usize,
+				"Schedule not removed",
+			);
+			assert_eq!(
+				Vesting::<T>::vesting_balance(&caller),
+				Some((100 * s).into()),
+				"Lock not recomputed after removal",
+			);
+		}
+	}
+
+	vested_transfer_via_trait {
+		let l in 0 .. MaxLocksOf::<T>::get();
+
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		let target: T::AccountId = account("target", 0, SEED);
+		// Give target existing locks
+		add_locks::<T>(&target, l as u8);
+
+		let transfer_amount = T::MinVestedTransfer::get();
+	}: {
+		// Exercise the `VestedTransfer` trait directly, the way a dependent pallet would,
+		// rather than dispatching the `vested_transfer` extrinsic.
+		<Module<T> as VestedTransfer<_>>::vested_transfer(
+			caller,
+			target.clone(),
+			transfer_amount,
+			1u32.into(),
+			1,
+			1u32.into(),
+		)?;
+	}
+	verify {
+		assert_eq!(
+			T::MinVestedTransfer::get(),
+			T::Currency::free_balance(&target),
+			"Transfer didn't happen",
+		);
+		assert_eq!(
+			Vesting::<T>::vesting_balance(&target),
+			Some(T::MinVestedTransfer::get()),
+			"Lock not created",
+		);
+	}
+
+	update_vesting_schedules {
+		let l in 0 .. MaxLocksOf::<T>::get() - 1;
+		let s in 0 .. T::MaxVestingSchedules::get() - 1;
+
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let caller_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(caller.clone());
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		add_locks::<T>(&caller, l as u8);
+		add_vesting_schedules::<T>(&caller, s + 1)?;
+
+		let new_schedules: Vec<_> = (0..s + 1).map(|_| VestingInfo {
+			starting_block: 1u32.into(),
+			period: 1u32.into(),
+			per_period: 10u32.into(),
+			period_count: 1,
+		}).collect();
+	}: _(RawOrigin::Root, caller_lookup, new_schedules)
+	verify {
+		assert_eq!(
+			Vesting::<T>::vesting(&caller).unwrap().len(),
+			(s + 1) as usize,
+			"Schedules not replaced",
+		);
+		assert_eq!(
+			Vesting::<T>::vesting_balance(&caller),
+			Some((10 * (s + 1)).into()),
+			"Lock not recomputed from the new schedules",
+		);
+	}
 }
 
 #[cfg(test)]
@@ -315,6 +504,11 @@ mod tests {
 			assert_ok!(test_benchmark_vest_other_unlocked::<Test>());
 			assert_ok!(test_benchmark_vested_transfer::<Test>());
 			assert_ok!(test_benchmark_force_vested_transfer::<Test>());
+			assert_ok!(test_benchmark_vest_with_many_schedules::<Test>());
+			assert_ok!(test_benchmark_merge_schedules::<Test>());
+			assert_ok!(test_benchmark_force_remove_vesting_schedule::<Test>());
+			assert_ok!(test_benchmark_vested_transfer_via_trait::<Test>());
+			assert_ok!(test_benchmark_update_vesting_schedules::<Test>());
 		});
 	}
 }
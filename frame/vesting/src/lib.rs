@@ -0,0 +1,571 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Vesting Pallet
+//!
+//! A simple pallet providing a means of placing a graded, period-based curve on an account's
+//! locked balance. A schedule releases `per_period` every `period` blocks for `period_count`
+//! releases starting at `starting_block`; a single-release schedule (`period_count == 1`) is a
+//! cliff. This pallet ensures that there is a lock in place preventing the balance to drop below
+//! the *unvested* amount for any reason other than transaction fee payment.
+//!
+//! As the amount vested increases over time, the amount unvested reduces. However, locks on
+//! transfer only check that the transaction will not take the balance below the unvested amount.
+//!
+//! An account can have multiple concurrent vesting schedules, up to `Config::MaxVestingSchedules`
+//! of them. Adding another vested transfer to an already-vesting account pushes a further
+//! schedule onto the account's list rather than failing; the two (or more) schedules can later be
+//! combined with `merge_schedules` once their locks overlap.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+use sp_runtime::{RuntimeDebug, DispatchResult, traits::{
+	StaticLookup, Zero, One, AtLeast32BitUnsigned, Convert, Saturating, SaturatedConversion,
+}};
+use frame_support::{
+	decl_module, decl_event, decl_storage, decl_error, ensure, transactional,
+	traits::{
+		Currency, LockableCurrency, WithdrawReasons, LockIdentifier,
+		ExistenceRequirement, Get,
+	},
+	weights::Weight,
+	BoundedVec,
+};
+use frame_system::{ensure_signed, ensure_root};
+use codec::{Encode, Decode};
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod tests;
+
+type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+pub trait WeightInfo {
+	fn vest_locked(l: u32) -> Weight;
+	fn vest_unlocked(l: u32) -> Weight;
+	fn vest_other_locked(l: u32) -> Weight;
+	fn vest_other_unlocked(l: u32) -> Weight;
+	fn vested_transfer(l: u32) -> Weight;
+	fn force_vested_transfer(l: u32) -> Weight;
+	fn vest_with_many_schedules(l: u32, s: u32) -> Weight;
+	fn merge_schedules(l: u32, s: u32) -> Weight;
+	fn force_remove_vesting_schedule(l: u32, s: u32) -> Weight;
+	fn update_vesting_schedules(l: u32, s: u32) -> Weight;
+}
+
+impl WeightInfo for () {
+	fn vest_locked(_l: u32) -> Weight { 0 }
+	fn vest_unlocked(_l: u32) -> Weight { 0 }
+	fn vest_other_locked(_l: u32) -> Weight { 0 }
+	fn vest_other_unlocked(_l: u32) -> Weight { 0 }
+	fn vested_transfer(_l: u32) -> Weight { 0 }
+	fn force_vested_transfer(_l: u32) -> Weight { 0 }
+	fn vest_with_many_schedules(_l: u32, _s: u32) -> Weight { 0 }
+	fn merge_schedules(_l: u32, _s: u32) -> Weight { 0 }
+	fn force_remove_vesting_schedule(_l: u32, _s: u32) -> Weight { 0 }
+	fn update_vesting_schedules(_l: u32, _s: u32) -> Weight { 0 }
+}
+
+/// Identifier for the lock this pallet places on the vested part of an account's balance.
+const VESTING_ID: LockIdentifier = *b"vesting ";
+
+/// Struct to encode the vesting schedule of a single account, split into `period_count`
+/// equal releases of `per_period` every `period` blocks starting at `starting_block`. A
+/// cliff-style schedule is just the special case `period_count == 1`.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct VestingInfo<Balance, BlockNumber> {
+	/// Starting block for unlocking(vesting).
+	pub starting_block: BlockNumber,
+	/// Number of blocks between each release.
+	pub period: BlockNumber,
+	/// Amount released every `period` blocks.
+	pub per_period: Balance,
+	/// Number of releases, after which the schedule is fully vested.
+	pub period_count: u32,
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy, BlockNumber: AtLeast32BitUnsigned + Copy>
+	VestingInfo<Balance, BlockNumber>
+{
+	/// Total amount locked at genesis, i.e. `per_period * period_count`.
+	pub fn locked(&self) -> Balance {
+		self.per_period.saturating_mul(self.period_count.into())
+	}
+
+	/// Amount still locked at block `n`.
+	pub fn locked_at<BlockNumberToBalance: Convert<BlockNumber, Balance>>(
+		&self,
+		n: BlockNumber,
+	) -> Balance {
+		if n <= self.starting_block {
+			return self.locked();
+		}
+		// Number of whole periods elapsed since `starting_block`, capped at `period_count`
+		// once the schedule has fully vested.
+		let elapsed_periods = n.saturating_sub(self.starting_block) / self.period;
+		let elapsed_periods = BlockNumberToBalance::convert(elapsed_periods)
+			.min(Balance::from(self.period_count));
+		let released = self.per_period.saturating_mul(elapsed_periods);
+		self.locked().saturating_sub(released)
+	}
+}
+
+/// Allows other pallets to hand out funds on a vesting schedule without duplicating the
+/// transfer-then-lock logic that backs this pallet's own `vested_transfer`.
+pub trait VestedTransfer<AccountId> {
+	/// The currency that this vesting schedule's locked funds are denominated in.
+	type Currency: Currency<AccountId>;
+	/// The block number type used to express `starting_block` and `period`.
+	type Moment;
+
+	/// Transfer `per_period * period_count` from `source` to `target`, locking it on a
+	/// schedule that releases `per_period` every `period` blocks, starting at
+	/// `starting_block`.
+	fn vested_transfer(
+		source: AccountId,
+		target: AccountId,
+		per_period: <Self::Currency as Currency<AccountId>>::Balance,
+		period: Self::Moment,
+		period_count: u32,
+		starting_block: Self::Moment,
+	) -> DispatchResult;
+}
+
+/// Actual `MaxLocks` (on the underlying currency) to use in this pallet's benchmarks: the
+/// number of pre-existing locks on an account is bounded by the currency's own configuration.
+pub struct MaxLocksOf<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> Get<u32> for MaxLocksOf<T> {
+	fn get() -> u32 {
+		T::MaxLocks::get()
+	}
+}
+
+pub trait Config: frame_system::Config {
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
+
+	/// The currency trait.
+	type Currency: LockableCurrency<Self::AccountId>;
+
+	/// Convert the block number into a balance.
+	type BlockNumberToBalance: Convert<Self::BlockNumber, BalanceOf<Self>>;
+
+	/// The minimum amount transferred to call `vested_transfer`.
+	type MinVestedTransfer: Get<BalanceOf<Self>>;
+
+	/// The maximum number of locks that can exist on an account's balance, used only to bound
+	/// the weight of this pallet's dispatchables.
+	type MaxLocks: Get<u32>;
+
+	/// The maximum number of vesting schedules an account can have at once.
+	type MaxVestingSchedules: Get<u32>;
+
+	type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+	trait Store for Module<T: Config> as Vesting {
+		/// Information regarding the vesting of a given account.
+		pub Vesting get(fn vesting):
+			map hasher(blake2_128_concat) T::AccountId
+			=> Option<BoundedVec<VestingInfo<BalanceOf<T>, T::BlockNumber>, T::MaxVestingSchedules>>;
+	}
+	add_extra_genesis {
+		config(vesting): Vec<(T::AccountId, T::BlockNumber, T::BlockNumber, BalanceOf<T>)>;
+		build(|config: &GenesisConfig<T>| {
+			// Genesis schedules keep their historical linear-drip meaning: `liquid` unlocks
+			// in `length` equal per-block instalments starting at `begin`.
+			for &(ref who, begin, length, liquid) in config.vesting.iter() {
+				let length_as_balance = T::BlockNumberToBalance::convert(length);
+				let per_period = liquid / length_as_balance.max(One::one());
+				let period_count = length.saturated_into::<u32>().max(1);
+				Module::<T>::add_vesting_schedule(who, per_period, One::one(), period_count, begin)
+					.expect("Vesting schedule generated in genesis is valid");
+			}
+		})
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Config>::AccountId,
+		Balance = BalanceOf<T>,
+	{
+		/// The amount vested has been updated. This could indicate a change in funds available.
+		/// The balance given is the amount which is left unvested (and thus locked).
+		VestingUpdated(AccountId, Balance),
+		/// An \[account\] has become fully vested.
+		VestingCompleted(AccountId),
+	}
+);
+
+decl_error! {
+	pub enum Error for Module<T: Config> {
+		/// The account given is not vesting.
+		NotVesting,
+		/// The account already has `MaxVestingSchedules` count of schedules and thus
+		/// cannot add another one.
+		AtMaxVestingSchedules,
+		/// Amount being transferred is too low to create a vesting schedule.
+		AmountLow,
+		/// An index was out of bounds of the vesting schedules.
+		ScheduleIndexOutOfBounds,
+		/// Failed to create a new schedule because some parameter was invalid.
+		InvalidScheduleParams,
+		/// The schedules given to `update_vesting_schedules` would lock more than the
+		/// target's free balance.
+		ScheduleTotalOverflow,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Config> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		const MinVestedTransfer: BalanceOf<T> = T::MinVestedTransfer::get();
+
+		fn deposit_event() = default;
+
+		/// Unlock any vested funds of the sender account.
+		#[weight = T::WeightInfo::vest_locked(MaxLocksOf::<T>::get())
+			.max(T::WeightInfo::vest_unlocked(MaxLocksOf::<T>::get()))]
+		fn vest(origin) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::update_lock(who)
+		}
+
+		/// Unlock any vested funds of a `target` account.
+		#[weight = T::WeightInfo::vest_other_locked(MaxLocksOf::<T>::get())
+			.max(T::WeightInfo::vest_other_unlocked(MaxLocksOf::<T>::get()))]
+		fn vest_other(origin, target: <T::Lookup as StaticLookup>::Source) -> DispatchResult {
+			ensure_signed(origin)?;
+			let who = T::Lookup::lookup(target)?;
+			Self::update_lock(who)
+		}
+
+		/// Create a vested transfer, pushing a new schedule onto the target's existing ones
+		/// rather than requiring the target to have none.
+		#[weight = T::WeightInfo::vested_transfer(MaxLocksOf::<T>::get())]
+		fn vested_transfer(
+			origin,
+			target: <T::Lookup as StaticLookup>::Source,
+			schedule: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+		) -> DispatchResult {
+			let transactor = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(target)?;
+			<Self as VestedTransfer<_>>::vested_transfer(
+				transactor,
+				who,
+				schedule.per_period,
+				schedule.period,
+				schedule.period_count,
+				schedule.starting_block,
+			)
+		}
+
+		/// As `vested_transfer`, but root-only and allows withdrawing from any account.
+		#[weight = T::WeightInfo::force_vested_transfer(MaxLocksOf::<T>::get())]
+		fn force_vested_transfer(
+			origin,
+			source: <T::Lookup as StaticLookup>::Source,
+			target: <T::Lookup as StaticLookup>::Source,
+			schedule: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let target = T::Lookup::lookup(target)?;
+			let source = T::Lookup::lookup(source)?;
+			<Self as VestedTransfer<_>>::vested_transfer(
+				source,
+				target,
+				schedule.per_period,
+				schedule.period,
+				schedule.period_count,
+				schedule.starting_block,
+			)
+		}
+
+		/// Merge two of the sender's vesting schedules into a single one.
+		///
+		/// The merged schedule starts at the later of the two `starting_block`s (or the
+		/// current block, if both have already started) and runs until the later of the two
+		/// original end-blocks, locking the sum of what was still locked in each at the
+		/// current block.
+		#[weight = T::WeightInfo::merge_schedules(MaxLocksOf::<T>::get(), T::MaxVestingSchedules::get())]
+		fn merge_schedules(origin, schedule1_index: u32, schedule2_index: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			if schedule1_index == schedule2_index { return Ok(()) };
+			let schedule1_index = schedule1_index as usize;
+			let schedule2_index = schedule2_index as usize;
+
+			let schedules = Self::vesting(&who).ok_or(Error::<T>::NotVesting)?;
+			let schedule1 = *schedules.get(schedule1_index).ok_or(Error::<T>::ScheduleIndexOutOfBounds)?;
+			let schedule2 = *schedules.get(schedule2_index).ok_or(Error::<T>::ScheduleIndexOutOfBounds)?;
+
+			let now = <frame_system::Module<T>>::block_number();
+			let merged = Self::merge_vesting_info(now, schedule1, schedule2);
+
+			let mut remaining: Vec<_> = schedules.into_iter()
+				.enumerate()
+				.filter(|(i, _)| *i != schedule1_index && *i != schedule2_index)
+				.map(|(_, s)| s)
+				.collect();
+			if let Some(merged) = merged {
+				remaining.push(merged);
+			}
+
+			Self::write_vesting_schedules(&who, remaining)?;
+			Self::update_lock(who)
+		}
+
+		/// Remove a single vesting schedule from `target`, recomputing (or dropping) its lock.
+		///
+		/// This is a governance operation for unwinding erroneous or sanctioned vesting grants,
+		/// rather than something a vesting account can do to itself.
+		#[weight = T::WeightInfo::force_remove_vesting_schedule(
+			MaxLocksOf::<T>::get(),
+			T::MaxVestingSchedules::get(),
+		)]
+		fn force_remove_vesting_schedule(
+			origin,
+			target: <T::Lookup as StaticLookup>::Source,
+			schedule_index: u32,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let who = T::Lookup::lookup(target)?;
+			let schedule_index = schedule_index as usize;
+
+			let schedules = Self::vesting(&who).ok_or(Error::<T>::NotVesting)?;
+			ensure!(schedule_index < schedules.len(), Error::<T>::ScheduleIndexOutOfBounds);
+
+			let remaining: Vec<_> = schedules.into_iter()
+				.enumerate()
+				.filter(|(i, _)| *i != schedule_index)
+				.map(|(_, s)| s)
+				.collect();
+
+			Self::write_vesting_schedules(&who, remaining)?;
+			if Self::vesting(&who).is_none() {
+				Self::remove_vesting_schedule(&who);
+				Ok(())
+			} else {
+				Self::update_lock(who)
+			}
+		}
+
+		/// Force-replace all of `target`'s vesting schedules with `schedules`, recomputing the
+		/// lock from scratch.
+		///
+		/// Rejects the call if the new schedules would lock more than `target`'s current free
+		/// balance, since that amount could never actually vest.
+		#[weight = T::WeightInfo::update_vesting_schedules(
+			MaxLocksOf::<T>::get(),
+			schedules.len() as u32,
+		)]
+		fn update_vesting_schedules(
+			origin,
+			target: <T::Lookup as StaticLookup>::Source,
+			schedules: Vec<VestingInfo<BalanceOf<T>, T::BlockNumber>>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let who = T::Lookup::lookup(target)?;
+
+			for schedule in schedules.iter() {
+				// A zero `period` would divide-by-zero in `locked_at` the next time this
+				// schedule is evaluated (e.g. from `vest`); a zero `per_period` is simply
+				// a no-op schedule that shouldn't have been submitted.
+				ensure!(schedule.period >= One::one(), Error::<T>::InvalidScheduleParams);
+				ensure!(!schedule.per_period.is_zero(), Error::<T>::InvalidScheduleParams);
+			}
+
+			let total_locked = schedules.iter()
+				.fold(Zero::zero(), |acc: BalanceOf<T>, s| acc.saturating_add(s.locked()));
+			ensure!(total_locked <= T::Currency::free_balance(&who), Error::<T>::ScheduleTotalOverflow);
+
+			Self::write_vesting_schedules(&who, schedules)?;
+			if Self::vesting(&who).is_none() {
+				Self::remove_vesting_schedule(&who);
+				Ok(())
+			} else {
+				Self::update_lock(who)
+			}
+		}
+	}
+}
+
+impl<T: Config> Module<T> {
+	/// Recompute and apply the vesting lock (or remove it) from an account's current schedules.
+	fn update_lock(who: T::AccountId) -> DispatchResult {
+		let schedules = Self::vesting(&who).ok_or(Error::<T>::NotVesting)?;
+		let now = <frame_system::Module<T>>::block_number();
+		let locked_now = schedules.iter()
+			.fold(Zero::zero(), |acc: BalanceOf<T>, s| acc.saturating_add(s.locked_at::<T::BlockNumberToBalance>(now)));
+
+		if locked_now.is_zero() {
+			T::Currency::remove_lock(VESTING_ID, &who);
+			Vesting::<T>::remove(&who);
+			Self::deposit_event(RawEvent::VestingCompleted(who));
+		} else {
+			let reasons = WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE;
+			T::Currency::set_lock(VESTING_ID, &who, locked_now, reasons);
+			// Drop schedules that have fully vested; keep the still-active ones around.
+			let remaining: Vec<_> = schedules.into_iter()
+				.filter(|s| !s.locked_at::<T::BlockNumberToBalance>(now).is_zero())
+				.collect();
+			Self::write_vesting_schedules(&who, remaining)?;
+			Self::deposit_event(RawEvent::VestingUpdated(who, locked_now));
+		}
+		Ok(())
+	}
+
+	/// Overwrite `who`'s vesting schedules with `schedules`, clearing storage if empty.
+	fn write_vesting_schedules(
+		who: &T::AccountId,
+		schedules: Vec<VestingInfo<BalanceOf<T>, T::BlockNumber>>,
+	) -> DispatchResult {
+		if schedules.is_empty() {
+			Vesting::<T>::remove(who);
+		} else {
+			let bounded: BoundedVec<_, T::MaxVestingSchedules> = schedules.try_into()
+				.map_err(|_| Error::<T>::AtMaxVestingSchedules)?;
+			Vesting::<T>::insert(who, bounded);
+		}
+		Ok(())
+	}
+
+	/// Combine two schedules into one as of block `now`. Returns `None` if nothing would
+	/// remain locked in the merged schedule.
+	fn merge_vesting_info(
+		now: T::BlockNumber,
+		schedule1: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+		schedule2: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+	) -> Option<VestingInfo<BalanceOf<T>, T::BlockNumber>> {
+		let locked1 = schedule1.locked_at::<T::BlockNumberToBalance>(now);
+		let locked2 = schedule2.locked_at::<T::BlockNumberToBalance>(now);
+		let locked = locked1.saturating_add(locked2);
+		if locked.is_zero() {
+			return None;
+		}
+
+		let starting_block = if now >= schedule1.starting_block && now >= schedule2.starting_block {
+			now
+		} else {
+			schedule1.starting_block.max(schedule2.starting_block)
+		};
+
+		let end1 = schedule1.starting_block
+			.saturating_add(schedule1.period.saturating_mul(schedule1.period_count.into()));
+		let end2 = schedule2.starting_block
+			.saturating_add(schedule2.period.saturating_mul(schedule2.period_count.into()));
+		let ending_block = end1.max(end2).max(starting_block.saturating_add(One::one()));
+
+		let duration = ending_block.saturating_sub(starting_block);
+		let period_count = duration.saturated_into::<u32>().max(1);
+		let per_period = locked / BalanceOf::<T>::from(period_count);
+		// If `locked` doesn't divide evenly across `period_count` releases (including the
+		// case where `locked < period_count`), collapse to a single release of the full
+		// amount at `ending_block` rather than rounding `per_period` down and dropping the
+		// remainder from `per_period * period_count`.
+		let (per_period, period, period_count) =
+			if per_period.saturating_mul(period_count.into()) != locked {
+				(locked, duration, 1)
+			} else {
+				(per_period, One::one(), period_count)
+			};
+
+		Some(VestingInfo { starting_block, period, per_period, period_count })
+	}
+
+	/// Get the amount that is currently being vested and cannot be transferred out of this
+	/// account, across all of its schedules.
+	fn vesting_balance(who: &T::AccountId) -> Option<BalanceOf<T>> {
+		Self::vesting(who).map(|schedules| {
+			let now = <frame_system::Module<T>>::block_number();
+			let locked = schedules.iter()
+				.fold(Zero::zero(), |acc: BalanceOf<T>, s| acc.saturating_add(s.locked_at::<T::BlockNumberToBalance>(now)));
+			T::Currency::free_balance(who).min(locked)
+		})
+	}
+
+	/// Push a new vesting schedule onto `who`'s existing ones, updating the lock accordingly.
+	fn add_vesting_schedule(
+		who: &T::AccountId,
+		per_period: BalanceOf<T>,
+		period: T::BlockNumber,
+		period_count: u32,
+		starting_block: T::BlockNumber,
+	) -> DispatchResult {
+		if per_period.is_zero() || period_count == 0 { return Ok(()) }
+		ensure!(period >= One::one(), Error::<T>::InvalidScheduleParams);
+
+		let schedule = VestingInfo { starting_block, period, per_period, period_count };
+		let mut schedules = Self::vesting(who).map(|v| v.into_inner()).unwrap_or_default();
+		schedules.push(schedule);
+		Self::write_vesting_schedules(who, schedules)?;
+
+		let now = <frame_system::Module<T>>::block_number();
+		let all_schedules = Self::vesting(who).ok_or(Error::<T>::NotVesting)?;
+		let locked_now = all_schedules.iter()
+			.fold(Zero::zero(), |acc: BalanceOf<T>, s| acc.saturating_add(s.locked_at::<T::BlockNumberToBalance>(now)));
+		let reasons = WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE;
+		T::Currency::set_lock(VESTING_ID, who, locked_now, reasons);
+		Ok(())
+	}
+
+	fn remove_vesting_schedule(who: &T::AccountId) {
+		T::Currency::remove_lock(VESTING_ID, who);
+		Vesting::<T>::remove(who);
+	}
+}
+
+impl<T: Config> VestedTransfer<T::AccountId> for Module<T> {
+	type Currency = T::Currency;
+	type Moment = T::BlockNumber;
+
+	fn vested_transfer(
+		source: T::AccountId,
+		target: T::AccountId,
+		per_period: BalanceOf<T>,
+		period: T::BlockNumber,
+		period_count: u32,
+		starting_block: T::BlockNumber,
+	) -> DispatchResult {
+		Self::do_vested_transfer(source, target, per_period, period, period_count, starting_block)
+	}
+}
+
+impl<T: Config> Module<T> {
+	/// Transfer `per_period * period_count` from `source` to `target` and place it under a
+	/// vesting schedule, as a single transactional unit so a failed lock never leaves the
+	/// funds moved without a schedule to back them.
+	#[transactional]
+	fn do_vested_transfer(
+		source: T::AccountId,
+		target: T::AccountId,
+		per_period: BalanceOf<T>,
+		period: T::BlockNumber,
+		period_count: u32,
+		starting_block: T::BlockNumber,
+	) -> DispatchResult {
+		let locked = per_period.saturating_mul(period_count.into());
+		ensure!(locked >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
+		T::Currency::transfer(&source, &target, locked, ExistenceRequirement::AllowDeath)?;
+		Self::add_vesting_schedule(&target, per_period, period, period_count, starting_block)?;
+		Ok(())
+	}
+}
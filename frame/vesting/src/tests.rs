@@ -0,0 +1,248 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the vesting pallet.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_ok, assert_noop, impl_outer_origin, parameter_types, weights::Weight};
+use sp_core::H256;
+use sp_runtime::{
+	Perbill,
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup, Identity},
+};
+use std::cell::RefCell;
+
+impl_outer_origin! {
+	pub enum Origin for Test where system = frame_system {}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+impl frame_system::Config for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Call = ();
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = ();
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type PalletInfo = ();
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+parameter_types! {
+	pub const MaxLocks: u32 = 10;
+}
+thread_local! {
+	static EXISTENTIAL_DEPOSIT: RefCell<u64> = RefCell::new(0);
+}
+pub struct ExistentialDeposit;
+impl Get<u64> for ExistentialDeposit {
+	fn get() -> u64 {
+		EXISTENTIAL_DEPOSIT.with(|v| *v.borrow())
+	}
+}
+impl pallet_balances::Config for Test {
+	type Balance = u64;
+	type DustRemoval = ();
+	type Event = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type MaxLocks = MaxLocks;
+	type WeightInfo = ();
+}
+parameter_types! {
+	pub const MinVestedTransfer: u64 = 256 * 2;
+	pub const MaxVestingSchedules: u32 = 3;
+}
+impl Config for Test {
+	type Event = ();
+	type Currency = Balances;
+	type BlockNumberToBalance = Identity;
+	type MinVestedTransfer = MinVestedTransfer;
+	type MaxLocks = MaxLocks;
+	type MaxVestingSchedules = MaxVestingSchedules;
+	type WeightInfo = ();
+}
+type System = frame_system::Module<Test>;
+type Balances = pallet_balances::Module<Test>;
+type Vesting = Module<Test>;
+
+pub struct ExtBuilder {
+	existential_deposit: u64,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self { existential_deposit: 1 }
+	}
+}
+
+impl ExtBuilder {
+	pub fn existential_deposit(mut self, existential_deposit: u64) -> Self {
+		self.existential_deposit = existential_deposit;
+		self
+	}
+
+	pub fn build(self) -> sp_io::TestExternalities {
+		EXISTENTIAL_DEPOSIT.with(|v| *v.borrow_mut() = self.existential_deposit);
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+		pallet_balances::GenesisConfig::<Test> {
+			balances: vec![
+				(1, 10 * self.existential_deposit),
+				(2, 20 * self.existential_deposit),
+				(3, 30 * self.existential_deposit),
+				(4, 40 * self.existential_deposit),
+				(12, 10 * self.existential_deposit),
+			],
+		}.assimilate_storage(&mut t).unwrap();
+		// Account 1 has half its balance vesting block by block over 10 blocks from block 0.
+		GenesisConfig::<Test> {
+			vesting: vec![
+				(1, 0, 10, 5 * self.existential_deposit),
+			],
+		}.assimilate_storage(&mut t).unwrap();
+		t.into()
+	}
+}
+
+#[test]
+fn add_vesting_schedule_locks_the_graded_amount() {
+	ExtBuilder::default().existential_deposit(256).build().execute_with(|| {
+		System::set_block_number(1);
+		// 10 releases of 100, starting at block 1: locked 1000 at genesis.
+		assert_ok!(Vesting::add_vesting_schedule(&3, 100, 1, 10, 1));
+		assert_eq!(Vesting::vesting_balance(&3), Some(1000));
+
+		// Nothing unlocks until the first period boundary.
+		System::set_block_number(1);
+		assert_eq!(Vesting::vesting_balance(&3), Some(1000));
+
+		// Exactly one period in, one release has unlocked.
+		System::set_block_number(2);
+		assert_eq!(Vesting::vesting_balance(&3), Some(900));
+
+		// Fully vested once all periods have elapsed.
+		System::set_block_number(11);
+		assert_eq!(Vesting::vesting_balance(&3), Some(0));
+	});
+}
+
+#[test]
+fn cliff_schedule_unlocks_all_at_once() {
+	ExtBuilder::default().existential_deposit(256).build().execute_with(|| {
+		// A single-release (period_count == 1) schedule is a cliff: nothing unlocks
+		// before the period boundary, everything unlocks at/after it.
+		assert_ok!(Vesting::add_vesting_schedule(&4, 1000, 20, 1, 10));
+
+		System::set_block_number(29);
+		assert_eq!(Vesting::vesting_balance(&4), Some(1000));
+
+		System::set_block_number(30);
+		assert_eq!(Vesting::vesting_balance(&4), Some(0));
+	});
+}
+
+#[test]
+fn merge_schedules_combines_still_locked_amounts() {
+	ExtBuilder::default().existential_deposit(256).build().execute_with(|| {
+		assert_ok!(Vesting::add_vesting_schedule(&3, 100, 1, 10, 1));
+		assert_ok!(Vesting::add_vesting_schedule(&3, 100, 1, 10, 1));
+		assert_eq!(Vesting::vesting(&3).unwrap().len(), 2);
+		assert_eq!(Vesting::vesting_balance(&3), Some(2000));
+
+		assert_ok!(Vesting::merge_schedules(Origin::signed(3), 0, 1));
+		assert_eq!(Vesting::vesting(&3).unwrap().len(), 1);
+		// Still locked amount is preserved by the merge.
+		assert_eq!(Vesting::vesting_balance(&3), Some(2000));
+	});
+}
+
+#[test]
+fn force_remove_vesting_schedule_recomputes_the_lock() {
+	ExtBuilder::default().existential_deposit(256).build().execute_with(|| {
+		assert_ok!(Vesting::add_vesting_schedule(&3, 100, 1, 10, 1));
+		assert_ok!(Vesting::add_vesting_schedule(&3, 50, 1, 10, 1));
+		assert_eq!(Vesting::vesting_balance(&3), Some(1500));
+
+		assert_ok!(Vesting::force_remove_vesting_schedule(Origin::root(), 3, 1));
+		assert_eq!(Vesting::vesting(&3).unwrap().len(), 1);
+		assert_eq!(Vesting::vesting_balance(&3), Some(1000));
+
+		assert_ok!(Vesting::force_remove_vesting_schedule(Origin::root(), 3, 0));
+		assert!(Vesting::vesting(&3).is_none());
+		assert_eq!(Vesting::vesting_balance(&3), None);
+	});
+}
+
+#[test]
+fn update_vesting_schedules_rejects_locking_more_than_free_balance() {
+	ExtBuilder::default().existential_deposit(256).build().execute_with(|| {
+		let too_much = VestingInfo {
+			starting_block: 1,
+			period: 1,
+			per_period: Balances::free_balance(&3) + 1,
+			period_count: 1,
+		};
+		assert_noop!(
+			Vesting::update_vesting_schedules(Origin::root(), 3, vec![too_much]),
+			Error::<Test>::ScheduleTotalOverflow,
+		);
+	});
+}
+
+#[test]
+fn update_vesting_schedules_recomputes_the_lock() {
+	ExtBuilder::default().existential_deposit(256).build().execute_with(|| {
+		assert_ok!(Vesting::add_vesting_schedule(&3, 100, 1, 10, 1));
+		assert_eq!(Vesting::vesting_balance(&3), Some(1000));
+
+		let replacement = VestingInfo {
+			starting_block: 1,
+			period: 1,
+			per_period: 200,
+			period_count: 2,
+		};
+		assert_ok!(Vesting::update_vesting_schedules(Origin::root(), 3, vec![replacement]));
+		assert_eq!(Vesting::vesting(&3).unwrap().len(), 1);
+		assert_eq!(Vesting::vesting_balance(&3), Some(400));
+	});
+}